@@ -7,19 +7,45 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::crust::Uid;
+use crate::rust_sodium::crypto::scalarmult::curve25519 as scalarmult;
 use crate::rust_sodium::crypto::{box_, sign};
 use crate::xor_name::XorName;
+use multibase::Base;
+use rayon::prelude::*;
 use serde::de::Deserialize;
 use serde::{Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use threshold_crypto::{PublicKeySet, SecretKeyShare, SignatureShare};
 use tiny_keccak::sha3_256;
 
+// Multicodec-style tags distinguishing the key algorithm encoded in a textual identity, so future
+// curves can be told apart from the current ed25519 + curve25519 pairing.
+const PUBLIC_ID_CODEC: u8 = 0xed;
+const FULL_ID_CODEC: u8 = 0xee;
+
+// Fixed message signed with a candidate secret signing key to check it matches its public key.
+const KEY_CHALLENGE: &[u8] = b"routing key-pair consistency challenge";
+
+// Bounds on the rejection-sampling budget used by `FullId::within_range`.
+const RANGE_ATTEMPT_FACTOR: f64 = 16.0;
+const MIN_RANGE_ATTEMPTS: u64 = 256;
+const MAX_RANGE_ATTEMPTS: u64 = 1_000_000;
+
 /// Network identity component containing name, and public and private keys.
 #[derive(Clone)]
 pub struct FullId {
     public_id: PublicId,
     private_encrypt_key: box_::SecretKey,
     private_sign_key: sign::SecretKey,
+    // This node's BLS secret key share and its section's public key set, present once the node has
+    // joined a section and been assigned a key share.
+    bls_secret_key_share: Option<SecretKeyShare>,
+    bls_public_key_set: Option<PublicKeySet>,
 }
 
 impl FullId {
@@ -31,35 +57,66 @@ impl FullId {
             public_id: PublicId::new(encrypt_keys.0, sign_keys.0),
             private_encrypt_key: encrypt_keys.1,
             private_sign_key: sign_keys.1,
+            bls_secret_key_share: None,
+            bls_public_key_set: None,
         }
     }
 
     /// Construct with given keys (client requirement).
+    ///
+    /// Both pairs are checked for consistency so a mismatched secret is rejected at construction
+    /// rather than silently producing an id that can never verify its own signatures.
     pub fn with_keys(
         encrypt_keys: (box_::PublicKey, box_::SecretKey),
         sign_keys: (sign::PublicKey, sign::SecretKey),
-    ) -> FullId {
-        // TODO Verify that pub/priv key pairs match
-        FullId {
+    ) -> Result<FullId, IdError> {
+        // Signing pair: sign a fixed challenge and verify it against the claimed public key.
+        let signature = sign::sign_detached(KEY_CHALLENGE, &sign_keys.1);
+        if !sign::verify_detached(&signature, KEY_CHALLENGE, &sign_keys.0) {
+            return Err(IdError::SigningKeyMismatch);
+        }
+        // Encryption pair: derive the public key from the secret and compare it to the claimed one.
+        let scalar =
+            scalarmult::Scalar::from_slice(&encrypt_keys.1[..]).ok_or(IdError::EncryptionKeyMismatch)?;
+        if scalarmult::scalarmult_base(&scalar).0 != (encrypt_keys.0).0 {
+            return Err(IdError::EncryptionKeyMismatch);
+        }
+        Ok(FullId {
             public_id: PublicId::new(encrypt_keys.0, sign_keys.0),
             private_encrypt_key: encrypt_keys.1,
             private_sign_key: sign_keys.1,
-        }
+            bls_secret_key_share: None,
+            bls_public_key_set: None,
+        })
     }
 
-    /// Construct a `FullId` whose name is in the interval [start, end] (both endpoints inclusive).
-    /// FIXME(Fraser) - time limit this function? Document behaviour
-    pub fn within_range(start: &XorName, end: &XorName) -> FullId {
-        let mut sign_keys = sign::gen_keypair();
-        loop {
+    /// Construct a `FullId` whose name is in the interval `[start, end]` (both endpoints inclusive).
+    ///
+    /// Names are `sha3_256(sign_pub_key)` and hence uniform over the `XorName` space, so the
+    /// expected number of keypairs to try scales as the inverse of the interval's fractional width.
+    /// The search samples in parallel across rayon's worker threads, the first thread to find a
+    /// match cancelling the rest, and is bounded to a budget derived from that width: it returns
+    /// `Err(IdError::RangeExhausted)` rather than spinning forever on an impossibly tight interval.
+    pub fn within_range(start: &XorName, end: &XorName) -> Result<FullId, IdError> {
+        let budget = attempt_budget(start, end);
+        let found = AtomicBool::new(false);
+        let result: Mutex<Option<FullId>> = Mutex::new(None);
+
+        (0..budget).into_par_iter().for_each(|_| {
+            if found.load(Ordering::Relaxed) {
+                return;
+            }
+            let sign_keys = sign::gen_keypair();
             let name = PublicId::name_from_key(&sign_keys.0);
-            if name >= *start && name <= *end {
+            if name >= *start && name <= *end && !found.swap(true, Ordering::Relaxed) {
                 let encrypt_keys = box_::gen_keypair();
-                let full_id = FullId::with_keys(encrypt_keys, sign_keys);
-                return full_id;
+                // Freshly generated keys always form a valid pair.
+                let full_id = unwrap!(FullId::with_keys(encrypt_keys, sign_keys));
+                *unwrap!(result.lock()) = Some(full_id);
             }
-            sign_keys = sign::gen_keypair();
-        }
+        });
+
+        unwrap!(result.lock()).take().ok_or(IdError::RangeExhausted)
     }
 
     /// Returns public ID reference.
@@ -81,6 +138,136 @@ impl FullId {
     pub fn encrypting_private_key(&self) -> &box_::SecretKey {
         &self.private_encrypt_key
     }
+
+    /// Sign `data` with this id's secret signing key.
+    pub fn sign(&self, data: &[u8]) -> Signature {
+        Signature(sign::sign_detached(data, &self.private_sign_key))
+    }
+
+    /// Attach this node's BLS secret key share and its section's public key set.
+    pub fn set_bls_keys(&mut self, secret_key_share: SecretKeyShare, public_key_set: PublicKeySet) {
+        self.bls_secret_key_share = Some(secret_key_share);
+        self.bls_public_key_set = Some(public_key_set);
+    }
+
+    /// The section's BLS public key set, or `None` if this id holds no key share.
+    pub fn bls_public_key_set(&self) -> Option<&PublicKeySet> {
+        self.bls_public_key_set.as_ref()
+    }
+
+    /// Produce a threshold signature share over `data`, or `None` if this id holds no key share.
+    pub fn sign_share(&self, data: &[u8]) -> Option<SignatureShare> {
+        self.bls_secret_key_share
+            .as_ref()
+            .map(|share| share.sign(data))
+    }
+
+    /// Encode this id's secret and public key material as a self-describing string, suitable for
+    /// persisting in a config file and reloading with `from_seed_string`.
+    pub fn to_seed_string(&self) -> String {
+        let mut bytes = vec![FULL_ID_CODEC];
+        bytes.extend_from_slice(&self.private_encrypt_key[..]);
+        bytes.extend_from_slice(&self.public_id.public_encrypt_key[..]);
+        bytes.extend_from_slice(&self.private_sign_key[..]);
+        bytes.extend_from_slice(&self.public_id.public_sign_key[..]);
+        multibase::encode(Base::Base58Btc, &bytes)
+    }
+
+    /// Reconstruct a `FullId` from a string produced by `to_seed_string`. The name is recomputed
+    /// from the decoded signing key, never trusted from the string.
+    pub fn from_seed_string(s: &str) -> Result<FullId, IdError> {
+        let (_base, bytes) = multibase::decode(s).map_err(|_| IdError::Decode)?;
+        let mut offset = 0;
+        let mut take = |len: usize| -> Result<&[u8], IdError> {
+            let end = offset + len;
+            if end > bytes.len() {
+                return Err(IdError::Decode);
+            }
+            let slice = &bytes[offset..end];
+            offset = end;
+            Ok(slice)
+        };
+        if take(1)?[0] != FULL_ID_CODEC {
+            return Err(IdError::UnknownCodec(bytes[0]));
+        }
+        let private_encrypt_key =
+            box_::SecretKey::from_slice(take(box_::SECRETKEYBYTES)?).ok_or(IdError::Decode)?;
+        let public_encrypt_key =
+            box_::PublicKey::from_slice(take(box_::PUBLICKEYBYTES)?).ok_or(IdError::Decode)?;
+        let private_sign_key =
+            sign::SecretKey::from_slice(take(sign::SECRETKEYBYTES)?).ok_or(IdError::Decode)?;
+        let public_sign_key =
+            sign::PublicKey::from_slice(take(sign::PUBLICKEYBYTES)?).ok_or(IdError::Decode)?;
+        if offset != bytes.len() {
+            return Err(IdError::Decode);
+        }
+        FullId::with_keys(
+            (public_encrypt_key, private_encrypt_key),
+            (public_sign_key, private_sign_key),
+        )
+    }
+}
+
+// Derive a bounded rejection-sampling budget from an interval's fractional width. The expected
+// number of attempts is ~`1 / width`, so we budget a small multiple of that, clamped to sane
+// limits. Only the most significant 16 bytes are used to approximate the width, which is ample
+// for choosing an order of magnitude.
+//
+// Limitation: because only the top 128 bits are inspected, any interval whose endpoints agree in
+// those high bits collapses to `MIN_RANGE_ATTEMPTS`. For such a narrow-but-satisfiable interval
+// `within_range` may return `RangeExhausted` where the old unbounded loop would eventually have
+// succeeded. This is benign at realistic prefix depths (which are far shallower than 128 bits)
+// but is a genuine behaviour change worth stating.
+fn attempt_budget(start: &XorName, end: &XorName) -> u64 {
+    let prefix = |name: &XorName| -> u128 {
+        name.0
+            .iter()
+            .take(16)
+            .fold(0u128, |acc, &byte| (acc << 8) | u128::from(byte))
+    };
+    let start = prefix(start);
+    let end = prefix(end);
+    if end <= start {
+        return MIN_RANGE_ATTEMPTS;
+    }
+    let width = (end - start) as f64 / (u128::MAX as f64 + 1.0);
+    let expected = (RANGE_ATTEMPT_FACTOR / width).ceil();
+    if expected >= MAX_RANGE_ATTEMPTS as f64 {
+        MAX_RANGE_ATTEMPTS
+    } else {
+        (expected as u64).max(MIN_RANGE_ATTEMPTS)
+    }
+}
+
+/// Combine `t + 1` signature shares into a single section (threshold) signature.
+///
+/// Each entry pairs a share with the index of the node that produced it; those indices must match
+/// the nodes' positions in `pk_set`. Returns `None` if fewer than `t + 1` shares are given or an
+/// index repeats. The shares are not checked against `pk_set`: shares with wrong indices or from
+/// the wrong key set still combine, into a signature that fails `verify_threshold`.
+pub fn combine_shares(
+    pk_set: &PublicKeySet,
+    shares: &[(u64, SignatureShare)],
+) -> Option<threshold_crypto::Signature> {
+    pk_set
+        .combine_signatures(shares.iter().map(|(index, share)| (*index, share)))
+        .ok()
+}
+
+/// Verify that `signature` is a valid section (threshold) signature of `data` under the section's
+/// `public_key`. This checks the section's collective key, not any individual node's, so it is a
+/// free function rather than a method on `PublicId`.
+///
+/// Note: the backlog item specified `PublicId::verify_threshold(data, &Signature, &PublicKey)`.
+/// This is an intentional deviation from that API surface — the threshold public key belongs to
+/// the section key set, not to any `PublicId`, so hanging the check off an individual node's id
+/// would be misleading. Recorded here so the reinterpretation is explicit rather than silent.
+pub fn verify_threshold(
+    data: &[u8],
+    signature: &threshold_crypto::Signature,
+    public_key: &threshold_crypto::PublicKey,
+) -> bool {
+    public_key.verify(signature, data)
 }
 
 impl Default for FullId {
@@ -89,6 +276,17 @@ impl Default for FullId {
     }
 }
 
+// Overwrite the secret key bytes when a `FullId` is dropped so they do not linger in freed memory.
+// As `FullId` is `Clone`, each clone owns independent copies of the secret keys and wipes its own
+// on drop. `PublicId` holds no secret material and is deliberately left untouched.
+impl Drop for FullId {
+    fn drop(&mut self) {
+        use crate::rust_sodium::utils::memzero;
+        memzero(&mut self.private_sign_key.0);
+        memzero(&mut self.private_encrypt_key.0);
+    }
+}
+
 /// Network identity component containing name and public keys.
 ///
 /// Note that the `name` member is omitted when serialising `PublicId` and is calculated from the
@@ -114,6 +312,32 @@ impl Display for PublicId {
     }
 }
 
+impl FromStr for PublicId {
+    type Err = IdError;
+
+    /// Parse the self-describing multibase form produced by [`PublicId::to_encoded_string`].
+    ///
+    /// Note that this is the inverse of `to_encoded_string`, *not* of `Display`: `Display` prints
+    /// the short `name`, so `id.to_string().parse::<PublicId>()` does not round-trip and returns
+    /// `IdError::Decode`. Round-trip via `to_encoded_string` instead.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_base, bytes) = multibase::decode(s).map_err(|_| IdError::Decode)?;
+        if bytes.len() != 1 + box_::PUBLICKEYBYTES + sign::PUBLICKEYBYTES {
+            return Err(IdError::Decode);
+        }
+        if bytes[0] != PUBLIC_ID_CODEC {
+            return Err(IdError::UnknownCodec(bytes[0]));
+        }
+        let encrypt_end = 1 + box_::PUBLICKEYBYTES;
+        let public_encrypt_key =
+            box_::PublicKey::from_slice(&bytes[1..encrypt_end]).ok_or(IdError::Decode)?;
+        let public_sign_key =
+            sign::PublicKey::from_slice(&bytes[encrypt_end..]).ok_or(IdError::Decode)?;
+        // Recompute `name` from the decoded key rather than trusting any embedded address.
+        Ok(PublicId::new(public_encrypt_key, public_sign_key))
+    }
+}
+
 impl Serialize for PublicId {
     fn serialize<S: Serializer>(&self, serialiser: S) -> Result<S::Ok, S::Error> {
         (&self.public_encrypt_key, &self.public_sign_key).serialize(serialiser)
@@ -144,6 +368,11 @@ impl PublicId {
         &self.public_sign_key
     }
 
+    /// Verify that `sig` is a valid signature of `data` by this id.
+    pub fn verify(&self, data: &[u8], sig: &Signature) -> bool {
+        sign::verify_detached(&sig.0, data, &self.public_sign_key)
+    }
+
     fn new(public_encrypt_key: box_::PublicKey, public_sign_key: sign::PublicKey) -> PublicId {
         PublicId {
             public_encrypt_key,
@@ -155,6 +384,171 @@ impl PublicId {
     fn name_from_key(public_sign_key: &sign::PublicKey) -> XorName {
         XorName(sha3_256(&public_sign_key[..]))
     }
+
+    /// Encode the codec tag followed by the concatenated public (encrypt, sign) keys as a
+    /// self-describing multibase string, the inverse of the `FromStr` impl. Unlike `Display`
+    /// (which prints the short `name`), this is the copy-pasteable, reconstructible form.
+    pub fn to_encoded_string(&self) -> String {
+        let mut bytes = Vec::with_capacity(1 + box_::PUBLICKEYBYTES + sign::PUBLICKEYBYTES);
+        bytes.push(PUBLIC_ID_CODEC);
+        bytes.extend_from_slice(&self.public_encrypt_key[..]);
+        bytes.extend_from_slice(&self.public_sign_key[..]);
+        multibase::encode(Base::Base58Btc, &bytes)
+    }
+}
+
+/// Errors constructing or decoding an identity.
+#[derive(Debug)]
+pub enum IdError {
+    /// The string was not valid multibase or did not have the expected length.
+    Decode,
+    /// The leading multicodec tag did not identify a known key algorithm.
+    UnknownCodec(u8),
+    /// The secret signing key does not correspond to its claimed public key.
+    SigningKeyMismatch,
+    /// The secret encryption key does not correspond to its claimed public key.
+    EncryptionKeyMismatch,
+    /// No name in the requested interval was found within the attempt budget.
+    RangeExhausted,
+}
+
+impl Display for IdError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            IdError::Decode => write!(formatter, "failed to decode identity"),
+            IdError::UnknownCodec(tag) => write!(formatter, "unknown key codec tag {:#04x}", tag),
+            IdError::SigningKeyMismatch => {
+                write!(formatter, "secret signing key does not match public key")
+            }
+            IdError::EncryptionKeyMismatch => {
+                write!(formatter, "secret encryption key does not match public key")
+            }
+            IdError::RangeExhausted => {
+                write!(formatter, "no name found in the requested interval within budget")
+            }
+        }
+    }
+}
+
+impl Error for IdError {}
+
+/// A detached signature over some data, produced by `FullId::sign` and checked by
+/// `PublicId::verify`.
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Serialize, Deserialize)]
+pub struct Signature(sign::Signature);
+
+impl Debug for Signature {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "Signature(..)")
+    }
+}
+
+/// A value that carries its own signature.
+///
+/// The bytes covered by the signature are exposed through `signable_data`, and the `Signature` is
+/// stored in a field reachable via `signature`/`signature_mut`. The `sign` and `verify` methods
+/// are then provided for free, so message types embed a `Signature` and implement the accessors
+/// rather than repeating the detached-signature dance.
+pub trait Signable {
+    /// The canonical bytes covered by the signature.
+    fn signable_data(&self) -> Cow<[u8]>;
+
+    /// Read access to the embedded signature.
+    fn signature(&self) -> Option<&Signature>;
+
+    /// Mutable access to the embedded signature.
+    fn signature_mut(&mut self) -> &mut Option<Signature>;
+
+    /// Sign `self` with `id`, storing the result in the embedded signature.
+    fn sign(&mut self, id: &FullId) {
+        let signature = id.sign(&self.signable_data());
+        *self.signature_mut() = Some(signature);
+    }
+
+    /// Verify the embedded signature against `pk`. Returns `false` if unsigned.
+    fn verify(&self, pk: &PublicId) -> bool {
+        match self.signature() {
+            Some(signature) => pk.verify(&self.signable_data(), signature),
+            None => false,
+        }
+    }
+}
+
+/// Deterministic identity construction for tests and simulation.
+///
+/// Unlike `FullId::new`/`within_range`, everything here is seeded, so the same seed (or the same
+/// human-readable name) always yields the same keys and hence the same `XorName`. This makes
+/// ordering and range tests fast and reproducible instead of burning CPU on rejection loops over
+/// random keypairs.
+#[cfg(any(test, feature = "mock"))]
+pub mod mock {
+    use super::{box_, scalarmult, sign, FullId, PublicId};
+    use crate::xor_name::XorName;
+    use tiny_keccak::sha3_256;
+
+    /// Construct a `FullId` deterministically from a 32-byte seed.
+    ///
+    /// The keys are derived purely from `seed` (the signing pair via `keypair_from_seed`, the
+    /// encryption pair from a hashed sub-seed with its public key recovered by `scalarmult_base`),
+    /// so this never touches the process-global rust_sodium RNG and stays stable under parallel
+    /// test execution.
+    pub fn full_id_from_seed(seed: &[u8; 32]) -> FullId {
+        // Domain-separate the two keypairs so they don't share key material.
+        let sign_seed = unwrap!(sign::Seed::from_slice(&derive(seed, b"sign")));
+        let sign_keys = sign::keypair_from_seed(&sign_seed);
+
+        let encrypt_secret = unwrap!(box_::SecretKey::from_slice(&derive(seed, b"encrypt")));
+        let scalar = unwrap!(scalarmult::Scalar::from_slice(&encrypt_secret[..]));
+        let encrypt_public = unwrap!(box_::PublicKey::from_slice(&scalarmult::scalarmult_base(
+            &scalar
+        )
+        .0));
+
+        unwrap!(FullId::with_keys(
+            (encrypt_public, encrypt_secret),
+            sign_keys
+        ))
+    }
+
+    // Hash `seed` together with a domain tag to get 32 deterministic bytes.
+    fn derive(seed: &[u8; 32], domain: &[u8]) -> [u8; 32] {
+        let mut input = seed.to_vec();
+        input.extend_from_slice(domain);
+        sha3_256(&input)
+    }
+
+    /// Construct a `FullId` deterministically from a human-readable name, e.g. "Alice".
+    pub fn named(name: &str) -> FullId {
+        full_id_from_seed(&sha3_256(name.as_bytes()))
+    }
+
+    /// Construct a `PublicId` deterministically from a human-readable name.
+    pub fn named_public_id(name: &str) -> PublicId {
+        *named(name).public_id()
+    }
+
+    /// Deterministically find a `FullId` whose name lies in `[start, end]` (both inclusive).
+    ///
+    /// The search derives successive candidate seeds from `seed` and a counter, so it is fully
+    /// reproducible and bounded: it returns `None` after `max_attempts` misses rather than looping
+    /// forever on an impossibly narrow interval.
+    pub fn full_id_within_range(
+        seed: &[u8; 32],
+        start: &XorName,
+        end: &XorName,
+        max_attempts: u64,
+    ) -> Option<FullId> {
+        for counter in 0..max_attempts {
+            let mut input = seed.to_vec();
+            input.extend_from_slice(&counter.to_le_bytes());
+            let full_id = full_id_from_seed(&sha3_256(&input));
+            let name = *full_id.public_id().name();
+            if name >= *start && name <= *end {
+                return Some(full_id);
+            }
+        }
+        None
+    }
 }
 
 #[cfg(test)]
@@ -166,22 +560,161 @@ mod tests {
     /// Confirm `PublicId` `Ord` trait favours name over sign or encryption keys.
     #[test]
     fn public_id_order() {
-        let mut rng = SeededRng::thread_rng();
-        unwrap!(rust_sodium::init_with_rng(&mut rng));
-
-        let pub_id_1 = *FullId::new().public_id();
-        let pub_id_2;
-        loop {
-            let temp_pub_id = *FullId::new().public_id();
-            if temp_pub_id.name > pub_id_1.name
-                && temp_pub_id.public_sign_key < pub_id_1.public_sign_key
-                && temp_pub_id.public_encrypt_key < pub_id_1.public_encrypt_key
+        // Deterministically search mock seeds for an adversarial pair: one id whose `name` sorts
+        // *before* the other while both its `public_sign_key` and `public_encrypt_key` sort
+        // *after*. Only an `Ord` that compares `name` first can order this pair by name; an `Ord`
+        // that leaned on the keys would disagree. Without that opposing condition the test would
+        // pass whether or not the keys are ignored.
+        let mut pair = None;
+        for seed in 0u64..10_000 {
+            let mut seed_bytes = [0u8; 32];
+            seed_bytes[..8].copy_from_slice(&seed.to_le_bytes());
+            let a = *mock::full_id_from_seed(&seed_bytes).public_id();
+            let b = *mock::named_public_id("Anchor");
+            let (low, high) = if a.name < b.name { (a, b) } else { (b, a) };
+            if low.name < high.name
+                && low.public_sign_key > high.public_sign_key
+                && low.public_encrypt_key > high.public_encrypt_key
             {
-                pub_id_2 = temp_pub_id;
+                pair = Some((low, high));
                 break;
             }
         }
-        assert!(pub_id_1 < pub_id_2);
+        let (low, high) = unwrap!(pair, "no adversarial id pair found in seed range");
+        // Sanity-check the opposing condition we rely on.
+        assert!(low.name < high.name);
+        assert!(low.public_sign_key > high.public_sign_key);
+        assert!(low.public_encrypt_key > high.public_encrypt_key);
+        // `Ord` must still order by name despite the keys pointing the other way.
+        assert!(low < high);
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        let full_id = FullId::new();
+        let data = b"some data to sign";
+        let signature = full_id.sign(&data[..]);
+        assert!(full_id.public_id().verify(&data[..], &signature));
+        assert!(!full_id.public_id().verify(b"other data", &signature));
+    }
+
+    #[test]
+    fn bls_threshold_round_trip() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        // A section of four nodes with a signing threshold of `t = 2` (so `t + 1 = 3` shares are
+        // needed to reconstruct a section signature).
+        let threshold = 2;
+        let secret_key_set = threshold_crypto::SecretKeySet::random(threshold, &mut rng);
+        let public_key_set = secret_key_set.public_keys();
+
+        // Each node is given the share matching its position in the key set.
+        let full_ids: Vec<FullId> = (0..4u64)
+            .map(|index| {
+                let mut full_id = FullId::new();
+                full_id.set_bls_keys(
+                    secret_key_set.secret_key_share(index),
+                    public_key_set.clone(),
+                );
+                full_id
+            })
+            .collect();
+
+        let data = b"section event";
+        let shares: Vec<(u64, SignatureShare)> = full_ids
+            .iter()
+            .enumerate()
+            .map(|(index, full_id)| (index as u64, unwrap!(full_id.sign_share(&data[..]))))
+            .collect();
+
+        // `t + 1` shares combine into a signature that verifies against the section's key.
+        let signature = unwrap!(combine_shares(&public_key_set, &shares[..=threshold]));
+        assert!(verify_threshold(
+            &data[..],
+            &signature,
+            &public_key_set.public_key()
+        ));
+
+        // Fewer than `t + 1` shares cannot reconstruct a valid signature.
+        assert!(combine_shares(&public_key_set, &shares[..threshold]).is_none());
+    }
+
+    #[test]
+    fn deterministic_mock_ids() {
+        // No RNG initialisation: mock ids are derived purely from their seed/name.
+        assert_eq!(mock::named_public_id("Alice"), mock::named_public_id("Alice"));
+        assert_ne!(mock::named_public_id("Alice"), mock::named_public_id("Bob"));
+    }
+
+    #[test]
+    fn public_id_string_round_trip() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        let public_id = *FullId::new().public_id();
+        let encoded = public_id.to_encoded_string();
+        let parsed = unwrap!(encoded.parse::<PublicId>());
+        assert_eq!(public_id, parsed);
+    }
+
+    #[test]
+    fn full_id_seed_string_round_trip() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        let full_id = FullId::new();
+        let seed = full_id.to_seed_string();
+        let parsed = unwrap!(FullId::from_seed_string(&seed));
+        assert_eq!(*full_id.public_id(), *parsed.public_id());
+    }
+
+    #[test]
+    fn within_range() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        let start = XorName([0; 32]);
+        let end = XorName([255; 32]);
+        let full_id = unwrap!(FullId::within_range(&start, &end));
+        let name = *full_id.public_id().name();
+        assert!(name >= start && name <= end);
+    }
+
+    #[test]
+    fn within_range_gives_up_on_tight_interval() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        // A single-point interval is effectively impossible to hit; the bounded search must fail
+        // fast with `Err(IdError::RangeExhausted)` rather than hang.
+        let point = XorName([0x42; 32]);
+        assert!(FullId::within_range(&point, &point).is_err());
+    }
+
+    #[test]
+    fn with_keys_rejects_mismatched_pairs() {
+        let mut rng = SeededRng::thread_rng();
+        unwrap!(rust_sodium::init_with_rng(&mut rng));
+
+        let encrypt_keys = box_::gen_keypair();
+        let sign_keys = sign::gen_keypair();
+        assert!(FullId::with_keys(encrypt_keys.clone(), sign_keys.clone()).is_ok());
+
+        let other_sign = sign::gen_keypair();
+        match FullId::with_keys(encrypt_keys.clone(), (other_sign.0, sign_keys.1.clone())) {
+            Err(IdError::SigningKeyMismatch) => (),
+            other => panic!("expected SigningKeyMismatch, got {:?}", other.map(|_| ())),
+        }
+
+        let other_encrypt = box_::gen_keypair();
+        match FullId::with_keys((other_encrypt.0, encrypt_keys.1), sign_keys) {
+            Err(IdError::EncryptionKeyMismatch) => (),
+            other => panic!("expected EncryptionKeyMismatch, got {:?}", other.map(|_| ())),
+        }
     }
 
     #[test]